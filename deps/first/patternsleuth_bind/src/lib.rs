@@ -1,6 +1,14 @@
 #![allow(unused)]
 
-use std::{error::Error, sync::Arc, time::Instant};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap, HashSet, VecDeque},
+    error::Error,
+    hash::{Hash, Hasher},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, OnceLock},
+    time::Instant,
+};
+use iced_x86::{Decoder, DecoderOptions, Instruction, Mnemonic, OpKind};
 use patternsleuth_scanner::{Pattern, Xref};
 
 use patternsleuth::resolvers::{Resolution, ResolverFactory, DynResolverFactory, resolvers};
@@ -38,7 +46,20 @@ impl_collector! {
     }
 }
 
+// Just the EngineVersion resolver, for the cheap fingerprint probe below.
+// EngineVersion's Resolution is a struct of its own (major/minor), not the
+// newtype `u64` most resolvers produce, so it can't go through the generic
+// by-name `resolvers()` + `.get()` path the way a typed member of
+// UE4SSResolution can.
+impl_collector! {
+    #[derive(Debug, PartialEq)]
+    struct EngineVersionOnly {
+        engine_version: EngineVersion,
+    }
+}
+
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct LogLevel(extern "C" fn(*const u16));
 impl LogLevel {
     fn log(&self, msg: impl AsRef<str>) {
@@ -48,6 +69,7 @@ impl LogLevel {
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct PsCtx {
     default: LogLevel,
     normal: LogLevel,
@@ -57,6 +79,30 @@ pub struct PsCtx {
     config: PsScanConfig,
 }
 
+// Just the log channels out of a PsCtx, with no raw-pointer fields, so it can
+// be moved into a worker thread (see ps_scan_begin) without needing an
+// `unsafe impl Send`.
+#[derive(Clone, Copy)]
+struct LogChannels {
+    default: LogLevel,
+    normal: LogLevel,
+    verbose: LogLevel,
+    warning: LogLevel,
+    error: LogLevel,
+}
+
+impl From<&PsCtx> for LogChannels {
+    fn from(ctx: &PsCtx) -> Self {
+        LogChannels {
+            default: ctx.default,
+            normal: ctx.normal,
+            verbose: ctx.verbose,
+            warning: ctx.warning,
+            error: ctx.error,
+        }
+    }
+}
+
 macro_rules! _log_level {
     ($level:ident, $ctx:ident) => { $ctx.$level.log("") };
     ($level:ident, $ctx:ident, $($arg:tt)*) => { $ctx.$level.log(format!($($arg)*)) };
@@ -68,12 +114,14 @@ macro_rules! warning { ($ctx:ident $($arg:tt)*) => { _log_level!(warning, $ctx $
 macro_rules! error { ($ctx:ident $($arg:tt)*) => { _log_level!(error, $ctx $($arg)*) }; }
 
 #[repr(C)]
+#[derive(Clone, Copy, Default)]
 pub struct PsEngineVersion {
     major: u16,
     minor: u16,
 }
 
 #[repr(C)]
+#[derive(Clone, Copy)]
 pub struct PsScanConfig {
     guobject_array: bool,
     fname_tostring: bool,
@@ -85,9 +133,57 @@ pub struct PsScanConfig {
     fuobject_hash_tables_get: bool,
     gnatives: bool,
     console_manager_singleton: bool,
+    /// Skip the fingerprinted results cache and always resolve from scratch.
+    force_rescan: bool,
+    /// Directory to look in for user-supplied AOB signature overrides
+    /// (e.g. "UE4SS_Signatures"), or null to disable the fallback. It's
+    /// read into an owned path synchronously inside ps_scan/ps_scan_begin,
+    /// so the caller only needs to keep this pointer valid for the
+    /// duration of that call, not for the scan itself (including an async
+    /// scan started with ps_scan_begin, which may still be running long
+    /// after it returns).
+    signature_dir: *const i8,
+}
+
+// The resolver-enable flags out of a PsScanConfig, with `signature_dir`
+// resolved to an owned path elsewhere. Unlike PsScanConfig (which carries a
+// raw `signature_dir` pointer the caller owns), this has no raw-pointer
+// fields, so it's safe to move into a worker thread (see ps_scan_begin).
+#[derive(Clone, Copy, Default)]
+struct ScanFlags {
+    guobject_array: bool,
+    fname_tostring: bool,
+    fname_ctor_wchar: bool,
+    gmalloc: bool,
+    static_construct_object_internal: bool,
+    ftext_fstring: bool,
+    engine_version: bool,
+    fuobject_hash_tables_get: bool,
+    gnatives: bool,
+    console_manager_singleton: bool,
+    force_rescan: bool,
+}
+
+impl From<&PsScanConfig> for ScanFlags {
+    fn from(config: &PsScanConfig) -> Self {
+        ScanFlags {
+            guobject_array: config.guobject_array,
+            fname_tostring: config.fname_tostring,
+            fname_ctor_wchar: config.fname_ctor_wchar,
+            gmalloc: config.gmalloc,
+            static_construct_object_internal: config.static_construct_object_internal,
+            ftext_fstring: config.ftext_fstring,
+            engine_version: config.engine_version,
+            fuobject_hash_tables_get: config.fuobject_hash_tables_get,
+            gnatives: config.gnatives,
+            console_manager_singleton: config.console_manager_singleton,
+            force_rescan: config.force_rescan,
+        }
+    }
 }
 
 #[repr(C)]
+#[derive(Clone, Copy, Default)]
 pub struct PsScanResults {
     guobject_array: u64,
     fname_tostring: u64,
@@ -110,57 +206,265 @@ impl std::fmt::Display for ScanErrors {
     }
 }
 
-pub fn ps_scan_internal(ctx: &PsCtx, results: &mut PsScanResults) -> Result<(), Box<dyn Error>> {
-    default!(ctx, "Reading image");
+// Fingerprinted results cache
+//
+// Scanning the whole image is wasteful when the executable hasn't changed
+// since the last launch. We fingerprint the code section plus the resolved
+// EngineVersion, and key a serialized copy of the last successful
+// PsScanResults off that fingerprint so a matching relaunch can skip
+// `exe.resolve(...)` entirely.
+
+const SCAN_CACHE_FILE_NAME: &str = "UE4SS_ScanCache.bin";
+
+fn engine_version_probe(res: &EngineVersion) -> u64 {
+    ((res.major as u64) << 16) | res.minor as u64
+}
+
+fn resolve_engine_version_quick(exe: &impl patternsleuth::process::Image) -> Option<u64> {
+    let resolution = exe.resolve(EngineVersionOnly::resolver()).ok()?;
+    let res = resolution.engine_version.ok()?;
+    Some(engine_version_probe(&res))
+}
+
+// Cache entries are keyed on the image plus which resolvers were actually
+// requested, so disabling a member for one scan and enabling it for the
+// next can't hit a cache entry that never resolved it.
+fn image_fingerprint(
+    exe: &impl patternsleuth::process::Image,
+    engine_version_probe: u64,
+    config: &ScanFlags,
+) -> Option<u64> {
+    let text = exe
+        .memory
+        .sections()
+        .find(|section| is_code_section_name(section.name()))?;
+
+    let mut hasher = DefaultHasher::new();
+    text.data().hash(&mut hasher);
+    (text.data().len() as u64).hash(&mut hasher);
+    engine_version_probe.hash(&mut hasher);
+    config.guobject_array.hash(&mut hasher);
+    config.fname_tostring.hash(&mut hasher);
+    config.fname_ctor_wchar.hash(&mut hasher);
+    config.gmalloc.hash(&mut hasher);
+    config.static_construct_object_internal.hash(&mut hasher);
+    config.ftext_fstring.hash(&mut hasher);
+    config.engine_version.hash(&mut hasher);
+    config.fuobject_hash_tables_get.hash(&mut hasher);
+    config.gnatives.hash(&mut hasher);
+    config.console_manager_singleton.hash(&mut hasher);
+    Some(hasher.finish())
+}
+
+fn read_scan_cache(path: &Path) -> Option<(u64, PsScanResults)> {
+    let bytes = std::fs::read(path).ok()?;
+    if bytes.len() != 8 + std::mem::size_of::<PsScanResults>() {
+        return None;
+    }
+
+    let fingerprint = u64::from_le_bytes(bytes[0..8].try_into().ok()?);
+    let mut results = PsScanResults::default();
+    unsafe {
+        std::ptr::copy_nonoverlapping(
+            bytes[8..].as_ptr(),
+            &mut results as *mut PsScanResults as *mut u8,
+            std::mem::size_of::<PsScanResults>(),
+        );
+    }
+    Some((fingerprint, results))
+}
+
+fn write_scan_cache(path: &Path, fingerprint: u64, results: &PsScanResults) -> std::io::Result<()> {
+    let mut bytes = Vec::with_capacity(8 + std::mem::size_of::<PsScanResults>());
+    bytes.extend_from_slice(&fingerprint.to_le_bytes());
+    unsafe {
+        bytes.extend_from_slice(std::slice::from_raw_parts(
+            results as *const PsScanResults as *const u8,
+            std::mem::size_of::<PsScanResults>(),
+        ));
+    }
+    std::fs::write(path, bytes)
+}
+
+/// Delete the on-disk fingerprinted scan cache, if any.
+#[no_mangle]
+pub extern "C" fn ps_scan_clear_cache() -> bool {
+    std::fs::remove_file(SCAN_CACHE_FILE_NAME).is_ok()
+}
+
+pub fn ps_scan_internal(
+    log: &LogChannels,
+    flags: &ScanFlags,
+    signature_dir: Option<&Path>,
+    results: &mut PsScanResults,
+) -> Result<(), Box<dyn Error>> {
+    default!(log, "Reading image");
 
     let exe = patternsleuth::process::internal::read_image()?;
 
-    default!(ctx, "Starting scan");
+    let cache_path = Path::new(SCAN_CACHE_FILE_NAME);
+    if !flags.force_rescan {
+        if let Some(engine_version_probe) = resolve_engine_version_quick(&exe) {
+            if let Some(fingerprint) = image_fingerprint(&exe, engine_version_probe, flags) {
+                if let Some((cached_fingerprint, cached_results)) = read_scan_cache(cache_path) {
+                    if cached_fingerprint == fingerprint {
+                        default!(log, "Image fingerprint matches cache, skipping scan");
+                        *results = cached_results;
+                        return Ok(());
+                    }
+                }
+            }
+        }
+    }
+
+    default!(log, "Starting scan");
     let before = Instant::now();
     let resolution = exe.resolve(UE4SSResolution::resolver())?;
-    default!(ctx, "Scan finished in {:?}", before.elapsed());
+    default!(log, "Scan finished in {:?}", before.elapsed());
 
     let mut errors = ScanErrors::default();
 
+    // Look for a user-supplied AOB override in `UE4SS_Signatures/{file_name}`
+    // for a resolver that just failed. Each non-empty, non-comment line is
+    // one pattern in `Pattern::new` syntax, optionally followed by `+<hex
+    // offset>` and/or `R` to dereference a trailing rip-relative disp32
+    // after matching (value = match + offset + 4 + disp). The matches of all
+    // lines are pooled; returns the address only if they come to exactly
+    // one distinct address.
+    let try_user_signature = |file_name: &str| -> Option<u64> {
+        let contents = std::fs::read_to_string(signature_dir?.join(file_name)).ok()?;
+
+        let read_disp32 = |addr: u64| -> Option<i32> {
+            exe.memory.sections().find_map(|section| {
+                let base = section.address() as u64;
+                let data = section.data();
+                if addr >= base && addr + 4 <= base + data.len() as u64 {
+                    let offset = (addr - base) as usize;
+                    Some(i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap()))
+                } else {
+                    None
+                }
+            })
+        };
+
+        let mut matches: Vec<u64> = Vec::new();
+        for line in contents.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') || line.starts_with("//") {
+                continue;
+            }
+
+            let mut pattern_tokens = Vec::new();
+            let mut line_offset: i64 = 0;
+            let mut follow_rip = false;
+            for tok in line.split_whitespace() {
+                if let Some(off) = tok.strip_prefix('+') {
+                    line_offset = i64::from_str_radix(off.trim_start_matches("0x"), 16).unwrap_or(0);
+                } else if tok.eq_ignore_ascii_case("R") {
+                    follow_rip = true;
+                } else {
+                    pattern_tokens.push(tok);
+                }
+            }
+            let Ok(pattern) = Pattern::new(&pattern_tokens.join(" ")) else {
+                continue;
+            };
+
+            for section in exe.memory.sections() {
+                let scan_results = patternsleuth_scanner::scan_pattern(
+                    &[&pattern],
+                    section.address() as usize,
+                    section.data(),
+                );
+                for &addr in &scan_results[0] {
+                    let mut addr = (addr as i64 + line_offset) as u64;
+                    if follow_rip {
+                        let Some(disp) = read_disp32(addr) else {
+                            continue;
+                        };
+                        addr = addr.wrapping_add(4).wrapping_add(disp as i64 as u64);
+                    }
+                    matches.push(addr);
+                }
+            }
+        }
+
+        matches.sort_unstable();
+        matches.dedup();
+        match matches.len() {
+            0 => {
+                warning!(log, "No match for user signature 'UE4SS_Signatures/{}'", file_name);
+                None
+            }
+            1 => Some(matches[0]),
+            n => {
+                warning!(
+                    log,
+                    "Ambiguous user signature 'UE4SS_Signatures/{}': {} matches",
+                    file_name,
+                    n
+                );
+                None
+            }
+        }
+    };
+
     macro_rules! handle {
         ($member:ident, $name:literal, $lua:literal) => {
             handle!($member, $name, $lua, false);
         };
         ($member:ident, $name:literal, $lua:literal, $optional:expr) => {
-            if ctx.config.$member {
+            if flags.$member {
                 match resolution.$member {
                     Ok(res) => {
-                        default!(ctx, "Found {}: 0x{:x?}", $name, res.0);
+                        default!(log, "Found {}: 0x{:x?}", $name, res.0);
                         results.$member = res.0;
                     }
                     Err(err) => {
-                        warning!(ctx, "Failed to find {}: {err}", $name);
+                        warning!(log, "Failed to find {}: {err}", $name);
                         warning!(
-                            ctx,
+                            log,
                             "You can supply your own AOB in 'UE4SS_Signatures/{}'",
                             $lua
                         );
-                        results.$member = 0;
-                        // Only add to `errors` if it's not optional:
-                        if !$optional {
-                            errors.0.push(Box::new(err));
+                        match try_user_signature($lua) {
+                            Some(addr) => {
+                                default!(
+                                    log,
+                                    "Using user signature 'UE4SS_Signatures/{}' for {}: 0x{:x?}",
+                                    $lua,
+                                    $name,
+                                    addr
+                                );
+                                results.$member = addr;
+                            }
+                            None => {
+                                results.$member = 0;
+                                // Only add to `errors` if it's not optional:
+                                if !$optional {
+                                    errors.0.push(Box::new(err));
+                                }
+                            }
                         }
                     }
                 }
             }
         };
     }
-    if ctx.config.engine_version {
+    // Taken before the EngineVersion report below, which moves
+    // `resolution.engine_version` out.
+    let cache_probe = resolution.engine_version.as_ref().ok().map(engine_version_probe);
+    if flags.engine_version {
         match resolution.engine_version {
             Ok(res) => {
-                default!(ctx, "Found EngineVersion: {}", res);
+                default!(log, "Found EngineVersion: {}", res);
                 results.engine_version.major = res.major;
                 results.engine_version.minor = res.minor;
             }
             Err(err) => {
-                warning!(ctx, "Failed to find EngineVersion: {err}");
+                warning!(log, "Failed to find EngineVersion: {err}");
                 warning!(
-                    ctx,
+                    log,
                     "You need to override the engine version in 'UE4SS-settings.ini'."
                 );
                 errors.0.push(Box::new(err));
@@ -209,22 +513,181 @@ pub fn ps_scan_internal(ctx: &PsCtx, results: &mut PsScanResults) -> Result<(),
     );
 
     if errors.0.is_empty() {
+        // resolution.engine_version was already resolved by the batch scan
+        // above (it's always attempted regardless of `flags.engine_version`),
+        // so reuse it (via `cache_probe`) here instead of running a whole
+        // separate resolver pass.
+        if let Some(probe) = cache_probe {
+            if let Some(fingerprint) = image_fingerprint(&exe, probe, flags) {
+                if let Err(err) = write_scan_cache(cache_path, fingerprint, results) {
+                    warning!(log, "Failed to write scan cache: {err}");
+                }
+            }
+        }
         Ok(())
     } else {
         Err(Box::new(errors))
     }
 }
 
+// Parse the caller-owned `signature_dir` pointer into an owned path up
+// front, synchronously, while it's still guaranteed valid; nothing derived
+// from it needs to outlive this call.
+fn resolve_signature_dir(signature_dir: *const i8) -> Option<PathBuf> {
+    if signature_dir.is_null() {
+        return None;
+    }
+    unsafe { std::ffi::CStr::from_ptr(signature_dir) }
+        .to_str()
+        .ok()
+        .map(PathBuf::from)
+}
+
 #[no_mangle]
 pub extern "C" fn ps_scan(ctx: &PsCtx, results: &mut PsScanResults) -> bool {
-    if let Err(_err) = ps_scan_internal(ctx, results) {
-        warning!(ctx, "Scan failed\n");
+    let log = LogChannels::from(ctx);
+    let flags = ScanFlags::from(&ctx.config);
+    let signature_dir = resolve_signature_dir(ctx.config.signature_dir);
+
+    if let Err(_err) = ps_scan_internal(&log, &flags, signature_dir.as_deref(), results) {
+        warning!(log, "Scan failed\n");
         false
     } else {
         true
     }
 }
- 
+
+// Async scan jobs
+//
+// ps_scan blocks the caller for the whole image walk, which isn't great for
+// a host that wants to keep its own thread responsive. These entry points
+// run the same scan on a worker thread and hand back a job handle instead:
+//
+//  uint64_t job = ps_scan_begin(ctx, config);
+//  PsJobStatus status;
+//  while ((status = ps_scan_poll(job)) == PsJobStatus_Pending) { /* ...keep the UI alive... */ }
+//  if (status == PsJobStatus_Done) {
+//      PsScanResults results;
+//      ps_scan_take_results(job, &results);
+//  } else if (status == PsJobStatus_Failed) {
+//      // Required: a failed job's bookkeeping is only freed by a take/cancel
+//      // call, and ps_scan_take_results refuses to touch a failed job.
+//      ps_scan_cancel(job);
+//  }
+
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum PsJobStatus {
+    Pending = 0,
+    Done = 1,
+    Failed = 2,
+}
+
+struct ScanJob {
+    handle: Option<std::thread::JoinHandle<Result<PsScanResults, String>>>,
+    outcome: Option<Result<PsScanResults, String>>,
+}
+
+fn scan_jobs() -> &'static Mutex<HashMap<u64, ScanJob>> {
+    static JOBS: OnceLock<Mutex<HashMap<u64, ScanJob>>> = OnceLock::new();
+    JOBS.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+fn next_job_id() -> u64 {
+    static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+    NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Spawn a scan on a worker thread and return a job handle for ps_scan_poll/
+/// ps_scan_take_results/ps_scan_cancel. Never blocks.
+#[no_mangle]
+pub extern "C" fn ps_scan_begin(ctx: &PsCtx, config: PsScanConfig) -> u64 {
+    let log = LogChannels::from(ctx);
+    let flags = ScanFlags::from(&config);
+    let signature_dir = resolve_signature_dir(config.signature_dir);
+
+    let handle = std::thread::spawn(move || {
+        let mut results = PsScanResults::default();
+        ps_scan_internal(&log, &flags, signature_dir.as_deref(), &mut results)
+            .map(|()| results)
+            .map_err(|err| err.to_string())
+    });
+
+    let id = next_job_id();
+    scan_jobs().lock().unwrap().insert(
+        id,
+        ScanJob {
+            handle: Some(handle),
+            outcome: None,
+        },
+    );
+    id
+}
+
+/// Poll a job started with ps_scan_begin. Never blocks.
+#[no_mangle]
+pub extern "C" fn ps_scan_poll(job: u64) -> PsJobStatus {
+    let mut jobs = scan_jobs().lock().unwrap();
+    let Some(entry) = jobs.get_mut(&job) else {
+        return PsJobStatus::Failed;
+    };
+
+    if let Some(outcome) = &entry.outcome {
+        return match outcome {
+            Ok(_) => PsJobStatus::Done,
+            Err(_) => PsJobStatus::Failed,
+        };
+    }
+
+    let finished = entry.handle.as_ref().is_some_and(|h| h.is_finished());
+    if !finished {
+        return PsJobStatus::Pending;
+    }
+
+    let outcome = match entry.handle.take().unwrap().join() {
+        Ok(outcome) => outcome,
+        Err(_) => Err("scan worker panicked".to_string()),
+    };
+    let status = if outcome.is_ok() {
+        PsJobStatus::Done
+    } else {
+        PsJobStatus::Failed
+    };
+    entry.outcome = Some(outcome);
+    status
+}
+
+/// Move the results out of a finished job and drop its bookkeeping. Returns
+/// false if the job is unknown, still pending, or failed; a failed job is
+/// left in place for the caller to clean up with ps_scan_cancel instead.
+#[no_mangle]
+pub extern "C" fn ps_scan_take_results(job: u64, out: &mut PsScanResults) -> bool {
+    let mut jobs = scan_jobs().lock().unwrap();
+    let Some(entry) = jobs.get(&job) else {
+        return false;
+    };
+    let Some(Ok(_)) = &entry.outcome else {
+        return false;
+    };
+
+    let entry = jobs.remove(&job).unwrap();
+    *out = entry.outcome.unwrap().unwrap();
+    true
+}
+
+/// Drop bookkeeping for a job. The worker thread has no safe preemption
+/// point, so a running scan finishes in the background; its results are
+/// simply discarded instead of being handed back.
+///
+/// Required, not just for cancelling a pending job: ps_scan_take_results
+/// only evicts a job on the PsJobStatus_Done path, so a job that finished
+/// with PsJobStatus_Failed must be cleaned up with this call or it leaks
+/// for the life of the process.
+#[no_mangle]
+pub extern "C" fn ps_scan_cancel(job: u64) -> bool {
+    scan_jobs().lock().unwrap().remove(&job).is_some()
+}
+
 
 //  // Don't forget to get vtable size
 //  uint64_t vtable_size = ps_get_vtable_size(0x7FF612345678);
@@ -242,6 +705,168 @@ pub extern "C" fn ps_get_vtable_size(vtable_address: u64) -> u64 {
     }
 }
 
+// Bounded instruction emulator
+//
+// Some singletons are set via a short, fixed instruction sequence (a LEA of
+// a rip-relative global, an ADD of a struct offset, ...) that a raw AOB
+// can't pin down on its own. ps_emulate_resolve single-steps forward from
+// `start_address`, tracking register state in a tiny VM, and returns the
+// value of `target_reg` once it runs out of budget, hits control flow, or
+// hits something it doesn't model.
+//
+//  // target_reg uses x86-64 GP register encoding: RAX=0 ... RDI=7, R8=8 ... R15=15
+//  uint64_t gmalloc = ps_emulate_resolve(0x7FF612345678, /*RAX*/ 0, 16);
+
+fn is_immediate_kind(kind: OpKind) -> bool {
+    matches!(
+        kind,
+        OpKind::Immediate8
+            | OpKind::Immediate8to16
+            | OpKind::Immediate8to32
+            | OpKind::Immediate8to64
+            | OpKind::Immediate16
+            | OpKind::Immediate32
+            | OpKind::Immediate32to64
+            | OpKind::Immediate64
+    )
+}
+
+fn gp_register_index(reg: iced_x86::Register) -> Option<usize> {
+    use iced_x86::Register::*;
+    Some(match reg {
+        RAX => 0,
+        RCX => 1,
+        RDX => 2,
+        RBX => 3,
+        RSP => 4,
+        RBP => 5,
+        RSI => 6,
+        RDI => 7,
+        R8 => 8,
+        R9 => 9,
+        R10 => 10,
+        R11 => 11,
+        R12 => 12,
+        R13 => 13,
+        R14 => 14,
+        R15 => 15,
+        _ => return None,
+    })
+}
+
+/// Emulate forward from `start_address` for at most `max_steps` instructions,
+/// modeling MOV reg,imm / LEA reg,[rip+disp] / MOV reg,[rip+disp] / ADD|SUB
+/// reg,imm. Stops early on control flow or an unsupported instruction.
+/// `target_reg` is an x86-64 GP register index (RAX=0 ... R15=15). Returns 0
+/// if the register's value was never determined.
+#[no_mangle]
+pub extern "C" fn ps_emulate_resolve(start_address: u64, target_reg: u8, max_steps: u32) -> u64 {
+    if target_reg > 15 {
+        return 0;
+    }
+
+    let exe = match patternsleuth::process::internal::read_image() {
+        Ok(exe) => exe,
+        Err(_) => return 0,
+    };
+
+    let Some(section) = exe.memory.sections().find(|section| {
+        let base = section.address() as u64;
+        start_address >= base && start_address < base + section.data().len() as u64
+    }) else {
+        return 0;
+    };
+
+    let read_u64_at = |addr: u64| -> Option<u64> {
+        exe.memory.sections().find_map(|section| {
+            let base = section.address() as u64;
+            let data = section.data();
+            if addr >= base && addr + 8 <= base + data.len() as u64 {
+                let off = (addr - base) as usize;
+                Some(u64::from_le_bytes(data[off..off + 8].try_into().unwrap()))
+            } else {
+                None
+            }
+        })
+    };
+
+    let base = section.address() as u64;
+    let offset = (start_address - base) as usize;
+    let mut decoder = Decoder::with_ip(64, &section.data()[offset..], start_address, DecoderOptions::NONE);
+
+    let mut regs: [Option<u64>; 16] = [None; 16];
+    let mut steps_left = max_steps;
+    let mut instr = Instruction::default();
+
+    while steps_left > 0 && decoder.can_decode() {
+        decoder.decode_out(&mut instr);
+        steps_left -= 1;
+
+        if instr.flow_control() != iced_x86::FlowControl::Next {
+            break;
+        }
+
+        // Only full 64-bit GP register destinations are modeled; a narrower
+        // destination (e.g. `mov eax, ...`) zero-extends/truncates in ways we
+        // don't track, so treat it as unsupported and trap rather than
+        // silently leaving a stale value in that slot.
+        let handled = match instr.mnemonic() {
+            Mnemonic::Mov if instr.op0_kind() == OpKind::Register && is_immediate_kind(instr.op1_kind()) => {
+                match gp_register_index(instr.op0_register()) {
+                    Some(idx) => {
+                        regs[idx] = Some(instr.immediate(1));
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Mnemonic::Lea if instr.op0_kind() == OpKind::Register && instr.is_ip_rel_memory_operand() => {
+                match gp_register_index(instr.op0_register()) {
+                    Some(idx) => {
+                        regs[idx] = Some(instr.ip_rel_memory_address());
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Mnemonic::Mov if instr.op0_kind() == OpKind::Register && instr.is_ip_rel_memory_operand() => {
+                match gp_register_index(instr.op0_register()) {
+                    Some(idx) => {
+                        regs[idx] = read_u64_at(instr.ip_rel_memory_address());
+                        true
+                    }
+                    None => false,
+                }
+            }
+            Mnemonic::Add | Mnemonic::Sub
+                if instr.op0_kind() == OpKind::Register && is_immediate_kind(instr.op1_kind()) =>
+            {
+                match gp_register_index(instr.op0_register()) {
+                    Some(idx) => {
+                        if let Some(cur) = regs[idx] {
+                            let imm = instr.immediate(1);
+                            regs[idx] = Some(if instr.mnemonic() == Mnemonic::Add {
+                                cur.wrapping_add(imm)
+                            } else {
+                                cur.wrapping_sub(imm)
+                            });
+                        }
+                        true
+                    }
+                    None => false,
+                }
+            }
+            _ => false,
+        };
+
+        if !handled {
+            break;
+        }
+    }
+
+    regs[target_reg as usize].unwrap_or(0)
+}
+
 // // Pattern scanning
 //  uint64_t* pattern_results;
 //  size_t pattern_count;
@@ -482,6 +1107,222 @@ pub extern "C" fn ps_free_results(results: *mut u64, count: usize) {
     }
 }
 
+// Xref graph
+//
+// ps_build_xref_graph() walks each section named `.text` once, decoding each
+// instruction and recording an edge `from -> to` whenever it references
+// another address inside those sections (call/jmp targets, rip-relative
+// lea/mov); other sections are neither walked nor kept as edge targets. The
+// resulting adjacency lists (forward and transposed/reverse) are cached in
+// a process-wide slot so ps_xref_reachable/ps_xref_shortest_path can be
+// called repeatedly without rebuilding the graph.
+//
+//  if (ps_build_xref_graph()) {
+//      uint64_t* reach_results;
+//      size_t reach_count;
+//      ps_xref_reachable(0x7FF612345678, 8, &reach_results, &reach_count);
+//      ps_free_results(reach_results, reach_count);
+//  }
+
+struct XrefGraph {
+    forward: HashMap<u64, Vec<u64>>,
+    reverse: HashMap<u64, Vec<u64>>,
+}
+
+fn xref_graph() -> &'static Mutex<Option<XrefGraph>> {
+    static GRAPH: OnceLock<Mutex<Option<XrefGraph>>> = OnceLock::new();
+    GRAPH.get_or_init(|| Mutex::new(None))
+}
+
+fn is_code_section_name(name: &str) -> bool {
+    name.eq_ignore_ascii_case(".text")
+}
+
+/// Build (or rebuild) the cross-reference graph over the current image.
+/// Must be called before ps_xref_reachable/ps_xref_shortest_path.
+#[no_mangle]
+pub extern "C" fn ps_build_xref_graph() -> bool {
+    let exe = match patternsleuth::process::internal::read_image() {
+        Ok(exe) => exe,
+        Err(_) => return false,
+    };
+
+    let exec_ranges: Vec<(u64, u64)> = exe
+        .memory
+        .sections()
+        .filter(|section| is_code_section_name(section.name()))
+        .map(|section| {
+            let start = section.address() as u64;
+            (start, start + section.data().len() as u64)
+        })
+        .collect();
+    let in_exec_range = |addr: u64| exec_ranges.iter().any(|&(start, end)| addr >= start && addr < end);
+
+    let mut forward: HashMap<u64, Vec<u64>> = HashMap::new();
+    let mut reverse: HashMap<u64, Vec<u64>> = HashMap::new();
+    let mut seen_edges: HashSet<(u64, u64)> = HashSet::new();
+
+    for section in exe.memory.sections() {
+        if !is_code_section_name(section.name()) {
+            continue;
+        }
+
+        let base = section.address() as u64;
+        let data = section.data();
+        let mut decoder = Decoder::with_ip(64, data, base, DecoderOptions::NONE);
+        let mut instr = Instruction::default();
+
+        while decoder.can_decode() {
+            decoder.decode_out(&mut instr);
+            let from = instr.ip();
+
+            let to = match instr.mnemonic() {
+                Mnemonic::Call | Mnemonic::Jmp
+                    if matches!(instr.op0_kind(), OpKind::NearBranch64 | OpKind::NearBranch32) =>
+                {
+                    Some(instr.near_branch_target())
+                }
+                Mnemonic::Lea | Mnemonic::Mov if instr.is_ip_rel_memory_operand() => {
+                    Some(instr.ip_rel_memory_address())
+                }
+                _ => None,
+            };
+
+            if let Some(to) = to {
+                if in_exec_range(to) && seen_edges.insert((from, to)) {
+                    forward.entry(from).or_default().push(to);
+                    reverse.entry(to).or_default().push(from);
+                }
+            }
+        }
+    }
+
+    *xref_graph().lock().unwrap() = Some(XrefGraph { forward, reverse });
+    true
+}
+
+/// Find every address that transitively reaches `target_address` (i.e. every
+/// node with a directed path to it), bounded to `max_depth` hops.
+/// Requires a prior successful call to ps_build_xref_graph.
+#[no_mangle]
+pub extern "C" fn ps_xref_reachable(
+    target_address: u64,
+    max_depth: u32,
+    results: *mut *mut u64,
+    count: *mut usize,
+) -> bool {
+    if results.is_null() || count.is_null() {
+        return false;
+    }
+
+    let guard = xref_graph().lock().unwrap();
+    let Some(graph) = guard.as_ref() else {
+        return false;
+    };
+
+    let mut visited = HashSet::new();
+    visited.insert(target_address);
+    let mut queue = VecDeque::new();
+    queue.push_back((target_address, 0u32));
+    let mut out = Vec::new();
+
+    while let Some((addr, depth)) = queue.pop_front() {
+        if depth >= max_depth {
+            continue;
+        }
+        if let Some(preds) = graph.reverse.get(&addr) {
+            for &pred in preds {
+                if visited.insert(pred) {
+                    out.push(pred);
+                    queue.push_back((pred, depth + 1));
+                }
+            }
+        }
+    }
+
+    unsafe {
+        *count = out.len();
+        if out.is_empty() {
+            *results = std::ptr::null_mut();
+        } else {
+            let buffer = out.as_mut_ptr();
+            std::mem::forget(out);
+            *results = buffer;
+        }
+    }
+
+    true
+}
+
+/// Find the shortest call-chain from `from_address` to `to_address`, written
+/// into `results` as the ordered node chain (including both endpoints).
+/// Requires a prior successful call to ps_build_xref_graph.
+#[no_mangle]
+pub extern "C" fn ps_xref_shortest_path(
+    from_address: u64,
+    to_address: u64,
+    results: *mut *mut u64,
+    count: *mut usize,
+) -> bool {
+    if results.is_null() || count.is_null() {
+        return false;
+    }
+
+    let guard = xref_graph().lock().unwrap();
+    let Some(graph) = guard.as_ref() else {
+        return false;
+    };
+
+    let mut path = Vec::new();
+
+    if from_address == to_address {
+        path.push(from_address);
+    } else {
+        let mut visited = HashSet::new();
+        visited.insert(from_address);
+        let mut queue = VecDeque::new();
+        queue.push_back(from_address);
+        let mut predecessor: HashMap<u64, u64> = HashMap::new();
+        let mut found = false;
+
+        'bfs: while let Some(addr) = queue.pop_front() {
+            if let Some(succs) = graph.forward.get(&addr) {
+                for &succ in succs {
+                    if visited.insert(succ) {
+                        predecessor.insert(succ, addr);
+                        if succ == to_address {
+                            found = true;
+                            break 'bfs;
+                        }
+                        queue.push_back(succ);
+                    }
+                }
+            }
+        }
+
+        if !found {
+            return false;
+        }
+
+        path.push(to_address);
+        let mut cur = to_address;
+        while cur != from_address {
+            cur = predecessor[&cur];
+            path.push(cur);
+        }
+        path.reverse();
+    }
+
+    unsafe {
+        *count = path.len();
+        let buffer = path.as_mut_ptr();
+        std::mem::forget(path);
+        *results = buffer;
+    }
+
+    true
+}
+
 // Single resolver call (still uses batch system internally for caching benefits)
 //uint64_t guobject_array = ps_resolve_single("GUObjectArray");
 //uint64_t gmalloc = ps_resolve_single("GMalloc");